@@ -3,12 +3,22 @@ use pyo3::py::class as pyclass;
 use pyo3::py::methods as pymethods;
 
 use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::mem;
 use piston_window::*;
 
 type Scalar = f64;
 type Point = (Scalar, Scalar);
+type Matrix2d = [[f64; 3]; 2];
+
+/// A clip rectangle plus the coordinate origin offset it introduces.
+struct Viewport {
+    clip: (Scalar, Scalar, Scalar, Scalar),
+    origin: Point,
+}
 
 /// Represents an rgb color, with optional alpha.
+#[derive(Clone, Copy)]
 pub struct Color(i32, i32, i32, Option<f32>);
 
 impl Into<[f32; 4]> for Color {
@@ -52,6 +62,91 @@ impl<'a> FromPyObject<'a> for Color {
     }
 }
 
+/// The shape of a gradient fill.
+#[derive(Clone, Copy)]
+pub enum GradientKind {
+    Linear,
+    Radial,
+}
+
+/// How a region is filled: a flat color or a gradient.
+pub enum Fill {
+    Solid(Color),
+    Gradient {
+        kind: GradientKind,
+        stops: Vec<(f32, Color)>,
+        // `None` derives the gradient axis from the fill's bounding box.
+        matrix: Option<[[f64; 3]; 2]>,
+    },
+}
+
+impl<'a> FromPyObject<'a> for Fill {
+    fn extract(ob: &'a PyObjectRef) -> PyResult<Self> {
+        // A plain (r, g, b[, a]) tuple is a solid fill.
+        if let Ok(color) = Color::extract(ob) {
+            return Ok(Fill::Solid(color));
+        }
+
+        // A bare list of (ratio, color) stops is a linear gradient with a
+        // bounding-box-derived axis.
+        if let Ok(list) = PyList::try_from(ob) {
+            let stops = extract_stops(list)?;
+            return Ok(Fill::Gradient { kind: GradientKind::Linear, stops, matrix: None });
+        }
+
+        let dict = PyDict::try_from(ob)?;
+
+        let kind = match dict.get_item("kind") {
+            Some(k) => match k.extract::<String>()?.as_str() {
+                "linear" => GradientKind::Linear,
+                "radial" => GradientKind::Radial,
+                _ => return Err(exc::ValueError::new("Gradient kind must be 'linear' or 'radial'")),
+            },
+            None => GradientKind::Linear,
+        };
+
+        let stops = match dict.get_item("stops") {
+            Some(s) => extract_stops(PyList::try_from(s)?)?,
+            None => return Err(exc::ValueError::new("Gradient requires 'stops'")),
+        };
+
+        let matrix = match dict.get_item("matrix") {
+            Some(m) => Some(extract_matrix(m)?),
+            None => None,
+        };
+
+        Ok(Fill::Gradient { kind, stops, matrix })
+    }
+}
+
+/// Extracts a list of `(ratio, color)` gradient stops.
+fn extract_stops(l: &PyList) -> PyResult<Vec<(f32, Color)>> {
+    let mut stops: Vec<(f32, Color)> = Vec::with_capacity(l.len());
+    for i in 0..(l.len() as isize) {
+        let item = l.get_item(i);
+        let t = PyTuple::try_from(item)?;
+        if t.len() != 2 {
+            return Err(exc::ValueError::new("Each stop must be (ratio, color)"));
+        }
+        let slice = t.as_slice();
+        let ratio = slice[0].extract::<f32>(item.py())?;
+        let color = slice[1].extract::<Color>(item.py())?;
+        stops.push((ratio, color));
+    }
+    Ok(stops)
+}
+
+/// Extracts a `[[f64; 3]; 2]` affine matrix from a list of two 3-tuples.
+fn extract_matrix(ob: &PyObjectRef) -> PyResult<[[f64; 3]; 2]> {
+    let l = PyList::try_from(ob)?;
+    if l.len() != 2 {
+        return Err(exc::ValueError::new("Gradient matrix must have 2 rows"));
+    }
+    let r0 = l.get_item(0).extract::<(f64, f64, f64)>()?;
+    let r1 = l.get_item(1).extract::<(f64, f64, f64)>()?;
+    Ok([[r0.0, r0.1, r0.2], [r1.0, r1.1, r1.2]])
+}
+
 /// Represents a polygon in 2d.
 pub struct Poly(Vec<[Scalar; 2]>);
 
@@ -76,17 +171,30 @@ impl<'a> FromPyObject<'a> for Poly {
     }
 }
 
+/// A single command in a retained drawing path.
+pub enum PathCommand {
+    MoveTo(Point),
+    LineTo(Point),
+    CurveTo(Point, Point),
+}
+
 /// Represents a drawing action on the canvas.
 pub enum DrawAction {
     Clear(Color),
     Point(Point, Color),
-    Image,
+    Image {
+        texture_id: usize,
+        dest: Point,
+        scale: Option<(Scalar, Scalar)>,
+        src_rect: Option<[f64; 4]>,
+        tint: Option<Color>,
+    },
     Circle {
         center: Point,
         radius: Scalar,
         line_color: Color,
         line_width: Option<Scalar>,
-        fill_color: Option<Color>,
+        fill_color: Option<Fill>,
     },
     Arc {
         center: Point,
@@ -99,17 +207,362 @@ pub enum DrawAction {
         vertices: Poly,
         line_color: Color,
         line_width: Option<Scalar>,
-        fill_color: Option<Color>,
+        fill_color: Option<Fill>,
+    },
+    Path {
+        commands: Vec<PathCommand>,
+        line_style: Option<(Scalar, Color)>,
+        fill: Option<Fill>,
+    },
+    PushClip {
+        rect: (Scalar, Scalar, Scalar, Scalar),
     },
+    PopClip,
     Polyline,
     Line,
     Text,
 }
 
+/// Flattens a path into one vertex list per subpath, evaluating quadratic
+/// curves into line segments.
+fn flatten_path(commands: &[PathCommand]) -> Vec<Vec<[Scalar; 2]>> {
+    let mut subpaths: Vec<Vec<[Scalar; 2]>> = Vec::new();
+    let mut current: Vec<[Scalar; 2]> = Vec::new();
+    let mut pen: Point = (0.0, 0.0);
+
+    for cmd in commands {
+        match *cmd {
+            PathCommand::MoveTo(p) => {
+                if !current.is_empty() {
+                    subpaths.push(mem::replace(&mut current, Vec::new()));
+                }
+                pen = p;
+                current.push([p.0, p.1]);
+            }
+            PathCommand::LineTo(p) => {
+                pen = p;
+                current.push([p.0, p.1]);
+            }
+            PathCommand::CurveTo(ctrl, end) => {
+                // Subdivide B(t) = (1-t)^2.P0 + 2(1-t)t.C + t^2.P1 into steps
+                // scaled by the control-point distance.
+                let steps = curve_steps(pen, ctrl, end);
+                for i in 1..(steps + 1) {
+                    let t = i as Scalar / steps as Scalar;
+                    let mt = 1.0 - t;
+                    let x = mt * mt * pen.0 + 2.0 * mt * t * ctrl.0 + t * t * end.0;
+                    let y = mt * mt * pen.1 + 2.0 * mt * t * ctrl.1 + t * t * end.1;
+                    current.push([x, y]);
+                }
+                pen = end;
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        subpaths.push(current);
+    }
+    subpaths
+}
+
+/// Picks a subdivision count for a quadratic curve based on its rough length.
+fn curve_steps(p0: Point, ctrl: Point, p1: Point) -> usize {
+    let dist = |a: Point, b: Point| ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+    let len = dist(p0, ctrl) + dist(ctrl, p1);
+    ((len / 5.0) as usize).max(2).min(40)
+}
+
+/// Ear-clips a simple polygon into triangles, or returns `None` for
+/// degenerate input (zero area, collinear runs) so the caller can fall back
+/// to the convex draw path.
+fn triangulate(verts: &[[Scalar; 2]]) -> Option<Vec<[[Scalar; 2]; 3]>> {
+    let n = verts.len();
+    if n < 3 {
+        return None;
+    }
+
+    let area = signed_area(verts);
+    if area.abs() < 1e-9 {
+        return None;
+    }
+    // Interior tests use the polygon's own winding.
+    let ccw = area > 0.0;
+
+    let mut indices: Vec<usize> = (0..n).collect();
+    let mut triangles: Vec<[[Scalar; 2]; 3]> = Vec::with_capacity(n - 2);
+
+    let mut guard = 0;
+    let max_guard = n * n;
+    while indices.len() > 3 {
+        let m = indices.len();
+        let mut clipped = false;
+
+        for i in 0..m {
+            let i0 = indices[(i + m - 1) % m];
+            let i1 = indices[i];
+            let i2 = indices[(i + 1) % m];
+            let a = verts[i0];
+            let b = verts[i1];
+            let c = verts[i2];
+
+            if !is_convex(a, b, c, ccw) {
+                continue;
+            }
+
+            // The ear may not contain any other vertex.
+            let mut contains = false;
+            for &j in &indices {
+                if j == i0 || j == i1 || j == i2 {
+                    continue;
+                }
+                if point_in_triangle(verts[j], a, b, c) {
+                    contains = true;
+                    break;
+                }
+            }
+            if contains {
+                continue;
+            }
+
+            triangles.push([a, b, c]);
+            indices.remove(i);
+            clipped = true;
+            break;
+        }
+
+        guard += 1;
+        if !clipped || guard > max_guard {
+            return None;
+        }
+    }
+
+    triangles.push([verts[indices[0]], verts[indices[1]], verts[indices[2]]]);
+    Some(triangles)
+}
+
+/// Returns the signed area of a polygon; the sign encodes the winding.
+fn signed_area(verts: &[[Scalar; 2]]) -> Scalar {
+    let n = verts.len();
+    let mut sum = 0.0;
+    for i in 0..n {
+        let a = verts[i];
+        let b = verts[(i + 1) % n];
+        sum += a[0] * b[1] - b[0] * a[1];
+    }
+    sum / 2.0
+}
+
+/// Returns `true` if the corner `a, b, c` turns the same way as the winding.
+fn is_convex(a: [Scalar; 2], b: [Scalar; 2], c: [Scalar; 2], ccw: bool) -> bool {
+    let cross = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+    if ccw {
+        cross > 0.0
+    } else {
+        cross < 0.0
+    }
+}
+
+/// Barycentric-sign test for a point inside triangle `a, b, c`.
+fn point_in_triangle(p: [Scalar; 2], a: [Scalar; 2], b: [Scalar; 2], c: [Scalar; 2]) -> bool {
+    let sign = |p: [Scalar; 2], a: [Scalar; 2], b: [Scalar; 2]| {
+        (p[0] - b[0]) * (a[1] - b[1]) - (a[0] - b[0]) * (p[1] - b[1])
+    };
+    let d1 = sign(p, a, b);
+    let d2 = sign(p, b, c);
+    let d3 = sign(p, c, a);
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+/// Approximates a circle outline as a ring of `segments` vertices.
+fn circle_ring(center: Point, radius: Scalar, segments: usize) -> Vec<[Scalar; 2]> {
+    let mut ring = Vec::with_capacity(segments);
+    for i in 0..segments {
+        let theta = 2.0 * PI * (i as Scalar) / (segments as Scalar);
+        ring.push([center.0 + radius * theta.cos(), center.1 + radius * theta.sin()]);
+    }
+    ring
+}
+
+/// Fills a vertex ring with either a solid color or a tessellated gradient.
+fn fill_region(vertices: &[[Scalar; 2]], fill: &Fill, draw_state: &DrawState, transform: Matrix2d, g: &mut G2d) {
+    if vertices.len() < 3 {
+        return;
+    }
+    match *fill {
+        Fill::Solid(color) => {
+            let col: [f32; 4] = color.into();
+            // Ear-clip concave outlines into triangles; fall back to the
+            // convex path on degenerate input.
+            match triangulate(vertices) {
+                Some(triangles) => {
+                    for tri in &triangles {
+                        Polygon::new(col).draw(
+                            tri,
+                            draw_state,
+                            transform,
+                            g,
+                        );
+                    }
+                }
+                None => {
+                    Polygon::new(col).draw(
+                        vertices,
+                        draw_state,
+                        transform,
+                        g,
+                    );
+                }
+            }
+        }
+        Fill::Gradient { ref kind, ref stops, ref matrix } => {
+            let matrix = match *matrix {
+                Some(m) => m,
+                None => default_matrix(kind, vertices),
+            };
+            draw_gradient_fill(vertices, kind, stops, &matrix, draw_state, transform, g);
+        }
+    }
+}
+
+/// Tessellates a region into a triangle fan around its centroid and draws it
+/// with per-vertex gradient colors.
+fn draw_gradient_fill(vertices: &[[Scalar; 2]],
+                      kind: &GradientKind,
+                      stops: &[(f32, Color)],
+                      matrix: &[[f64; 3]; 2],
+                      draw_state: &DrawState,
+                      transform: Matrix2d,
+                      g: &mut G2d) {
+    let n = vertices.len();
+    let cx = vertices.iter().map(|v| v[0]).sum::<Scalar>() / n as Scalar;
+    let cy = vertices.iter().map(|v| v[1]).sum::<Scalar>() / n as Scalar;
+
+    // Applies the current transform to a canvas-space point.
+    let project = |x: Scalar, y: Scalar| -> [f32; 2] {
+        [
+            (transform[0][0] * x + transform[0][1] * y + transform[0][2]) as f32,
+            (transform[1][0] * x + transform[1][1] * y + transform[1][2]) as f32,
+        ]
+    };
+
+    let mut positions: Vec<[f32; 2]> = Vec::with_capacity(n * 3);
+    let mut colors: Vec<[f32; 4]> = Vec::with_capacity(n * 3);
+
+    let center_color = sample_stops(stops, gradient_param(kind, matrix, cx, cy));
+    for i in 0..n {
+        let a = vertices[i];
+        let b = vertices[(i + 1) % n];
+
+        positions.push(project(cx, cy));
+        colors.push(center_color);
+        positions.push(project(a[0], a[1]));
+        colors.push(sample_stops(stops, gradient_param(kind, matrix, a[0], a[1])));
+        positions.push(project(b[0], b[1]));
+        colors.push(sample_stops(stops, gradient_param(kind, matrix, b[0], b[1])));
+    }
+
+    g.tri_list_c(draw_state, |f| f(&positions, &colors));
+}
+
+/// Derives a gradient axis matrix from the fill's bounding box: linear along
+/// the box width, radial from the box centre to its corner.
+fn default_matrix(kind: &GradientKind, verts: &[[Scalar; 2]]) -> [[f64; 3]; 2] {
+    let mut minx = verts[0][0];
+    let mut maxx = minx;
+    let mut miny = verts[0][1];
+    let mut maxy = miny;
+    for v in verts {
+        minx = minx.min(v[0]);
+        maxx = maxx.max(v[0]);
+        miny = miny.min(v[1]);
+        maxy = maxy.max(v[1]);
+    }
+
+    match *kind {
+        GradientKind::Linear => {
+            let w = (maxx - minx).max(1.0);
+            [[w, 0.0, minx], [0.0, 1.0, miny]]
+        }
+        GradientKind::Radial => {
+            let cx = (minx + maxx) / 2.0;
+            let cy = (miny + maxy) / 2.0;
+            let r = (((maxx - minx).powi(2) + (maxy - miny).powi(2)).sqrt() / 2.0).max(1.0);
+            [[r, 0.0, cx], [0.0, 1.0, cy]]
+        }
+    }
+}
+
+/// Maps a canvas-space point to its 0..1 gradient parameter.
+fn gradient_param(kind: &GradientKind, matrix: &[[f64; 3]; 2], x: Scalar, y: Scalar) -> f32 {
+    let ox = matrix[0][2];
+    let oy = matrix[1][2];
+    let ax = matrix[0][0];
+    let ay = matrix[1][0];
+    let len2 = ax * ax + ay * ay;
+
+    let t = match *kind {
+        GradientKind::Linear => {
+            if len2 == 0.0 {
+                0.0
+            } else {
+                ((x - ox) * ax + (y - oy) * ay) / len2
+            }
+        }
+        GradientKind::Radial => {
+            if len2 == 0.0 {
+                0.0
+            } else {
+                ((x - ox).powi(2) + (y - oy).powi(2)).sqrt() / len2.sqrt()
+            }
+        }
+    };
+
+    t.max(0.0).min(1.0) as f32
+}
+
+/// Samples the interpolated color between the stops surrounding `t`.
+fn sample_stops(stops: &[(f32, Color)], t: f32) -> [f32; 4] {
+    if stops.is_empty() {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+    if t <= stops[0].0 {
+        return stops[0].1.into();
+    }
+    let last = stops.len() - 1;
+    if t >= stops[last].0 {
+        return stops[last].1.into();
+    }
+
+    for i in 0..last {
+        let (r0, c0) = (stops[i].0, stops[i].1);
+        let (r1, c1) = (stops[i + 1].0, stops[i + 1].1);
+        if t >= r0 && t <= r1 {
+            let f = if r1 > r0 { (t - r0) / (r1 - r0) } else { 0.0 };
+            let a: [f32; 4] = c0.into();
+            let b: [f32; 4] = c1.into();
+            return [
+                a[0] + (b[0] - a[0]) * f,
+                a[1] + (b[1] - a[1]) * f,
+                a[2] + (b[2] - a[2]) * f,
+                a[3] + (b[3] - a[3]) * f,
+            ];
+        }
+    }
+    stops[last].1.into()
+}
+
 /// Represents the drawable area of a frame.
 #[pyclass]
 pub struct Canvas {
     draw_queue: VecDeque<DrawAction>,
+    path: Vec<PathCommand>,
+    line_style: Option<(Scalar, Color)>,
+    fill: Option<Fill>,
+    textures: Vec<G2dTexture>,
+    factory: Option<GfxFactory>,
+    clip_stack: Vec<Viewport>,
     size: (u32, u32),
     token: PyToken,
 }
@@ -119,6 +572,12 @@ impl Canvas {
     pub fn new<'p>(py: &'p Python) -> Py<Canvas> {
         py.init(|token| Canvas {
             draw_queue: VecDeque::new(),
+            path: Vec::new(),
+            line_style: None,
+            fill: None,
+            textures: Vec::new(),
+            factory: None,
+            clip_stack: Vec::new(),
             size: (0, 0),
             token,
         }).unwrap()
@@ -129,12 +588,44 @@ impl Canvas {
         self.size = size;
     }
 
+    /// Wires the window's factory into the canvas so textures can be uploaded.
+    pub fn set_factory(&mut self, factory: GfxFactory) {
+        self.factory = Some(factory);
+    }
+
     /// Draws the `draw_queue` to the graphics context.
     pub fn draw_canvas(&mut self, c: &Context, g: &mut G2d) {
         clear([0.0, 0.0, 0.0, 1.0], g);
+        self.clip_stack.clear();
 
         while let Some(d) = self.draw_queue.pop_front() {
+            // Resolve the active clip rect and origin offset for this action.
+            let (draw_state, transform) = match self.clip_stack.last() {
+                Some(vp) => (
+                    DrawState::default().scissor(clip_scissor(vp.clip, c)),
+                    c.transform.trans(vp.origin.0, vp.origin.1),
+                ),
+                None => (DrawState::default(), c.transform),
+            };
+
             match d {
+                DrawAction::PushClip { rect } => {
+                    let (base_origin, base_clip) = match self.clip_stack.last() {
+                        Some(top) => (top.origin, Some(top.clip)),
+                        None => ((0.0, 0.0), None),
+                    };
+                    // The new rect is relative to the current origin.
+                    let origin = (base_origin.0 + rect.0, base_origin.1 + rect.1);
+                    let abs = (origin.0, origin.1, rect.2, rect.3);
+                    let clip = match base_clip {
+                        Some(parent) => intersect_rect(parent, abs),
+                        None => abs,
+                    };
+                    self.clip_stack.push(Viewport { clip, origin });
+                }
+                DrawAction::PopClip => {
+                    self.clip_stack.pop();
+                }
                 DrawAction::Clear(color) => {
                     clear(color.into(), g)
                 }
@@ -142,30 +633,76 @@ impl Canvas {
                     let square = rectangle::square(point.0, point.1, 1.0);
                     Rectangle::new(color.into()).draw(
                         square,
-                        &Default::default(),
-                        c.transform,
+                        &draw_state,
+                        transform,
                         g,
                     );
                 }
-                DrawAction::Image => {
-                    // TODO
+                DrawAction::Image { texture_id, dest, scale, src_rect, tint } => {
+                    if let Some(texture) = self.textures.get(texture_id) {
+                        let mut image = Image::new();
+                        if let Some(rect) = src_rect {
+                            image = image.src_rect(rect);
+                        }
+                        if let Some(tint) = tint {
+                            image = image.color(tint.into());
+                        }
+
+                        let mut t = transform.trans(dest.0, dest.1);
+                        if let Some((sx, sy)) = scale {
+                            t = t.scale(sx, sy);
+                        }
+
+                        image.draw(texture, &draw_state, t, g);
+                    }
                 }
-                DrawAction::Circle { center, radius, line_width, line_color, fill_color } => {
-                    let mut ellipse = Ellipse::new_border(
-                        line_color.into(),
-                        line_width.unwrap_or(1.0),
-                    );
-                    if let Some(fill_color) = fill_color {
-                        ellipse = ellipse.color(fill_color.into());
+                DrawAction::Path { commands, line_style, fill } => {
+                    let subpaths = flatten_path(&commands);
+
+                    if let Some(ref fill) = fill {
+                        for sub in &subpaths {
+                            fill_region(sub, fill, &draw_state, transform, g);
+                        }
                     }
 
+                    if let Some((width, line_color)) = line_style {
+                        let l = Line::new(line_color.into(), width);
+                        for sub in &subpaths {
+                            for i in 0..sub.len().saturating_sub(1) {
+                                let p1 = sub[i];
+                                let p2 = sub[i + 1];
+                                l.draw(
+                                    [p1[0], p1[1], p2[0], p2[1]],
+                                    &draw_state,
+                                    transform,
+                                    g,
+                                );
+                            }
+                        }
+                    }
+                }
+                DrawAction::Circle { center, radius, line_width, line_color, fill_color } => {
                     let circle = ellipse::circle(center.0, center.1, radius);
-                    ellipse.draw(
-                        circle,
-                        &Default::default(),
-                        c.transform,
-                        g,
-                    );
+
+                    match fill_color {
+                        // A solid fill draws as a single filled, bordered ellipse.
+                        Some(Fill::Solid(color)) => {
+                            Ellipse::new_border(line_color.into(), line_width.unwrap_or(1.0))
+                                .color(color.into())
+                                .draw(circle, &draw_state, transform, g);
+                        }
+                        // A gradient fill is tessellated, with the border drawn on top.
+                        Some(fill) => {
+                            let ring = circle_ring(center, radius, 48);
+                            fill_region(&ring, &fill, &draw_state, transform, g);
+                            Ellipse::new_border(line_color.into(), line_width.unwrap_or(1.0))
+                                .draw(circle, &draw_state, transform, g);
+                        }
+                        None => {
+                            Ellipse::new_border(line_color.into(), line_width.unwrap_or(1.0))
+                                .draw(circle, &draw_state, transform, g);
+                        }
+                    }
                 }
                 DrawAction::Arc { center, radius, line_width, line_color, bounds } => {
                     let circle = ellipse::circle(center.0, center.1, radius);
@@ -176,21 +713,15 @@ impl Canvas {
                         bounds.1,
                     ).draw(
                         circle,
-                        &Default::default(),
-                        c.transform,
+                        &draw_state,
+                        transform,
                         g,
                     );
                 }
                 DrawAction::Polygon { vertices, line_color, line_width, fill_color } => {
                     let slice = vertices.as_slice();
-                    if let Some(fill) = fill_color {
-                        // TODO: add support for concave polygons.
-                        Polygon::new(fill.into()).draw(
-                            slice,
-                            &Default::default(),
-                            c.transform,
-                            g,
-                        );
+                    if let Some(ref fill) = fill_color {
+                        fill_region(slice, fill, &draw_state, transform, g);
                     }
 
                     let l = Line::new(line_color.into(), line_width.unwrap_or(1.0));
@@ -200,8 +731,8 @@ impl Canvas {
                         let line: [Scalar; 4] = [p1[0], p1[1], p2[0], p2[1]];
                         l.draw(
                             line,
-                            &Default::default(),
-                            c.transform,
+                            &draw_state,
+                            transform,
                             g,
                         );
                     }
@@ -212,6 +743,42 @@ impl Canvas {
     }
 }
 
+/// Converts a logical clip rect into a framebuffer scissor box. gfx's scissor
+/// (GL `glScissor`) is framebuffer space with the origin at the bottom-left, so
+/// y is flipped; the draw size is used so the box survives hi-dpi scaling.
+fn clip_scissor(clip: (Scalar, Scalar, Scalar, Scalar), c: &Context) -> [u32; 4] {
+    let (draw_h, scale_x, scale_y) = match c.viewport {
+        Some(v) => {
+            let draw_w = v.draw_size[0] as Scalar;
+            let draw_h = v.draw_size[1] as Scalar;
+            let win_w = v.window_size[0] as Scalar;
+            let win_h = v.window_size[1] as Scalar;
+            let scale_x = if win_w > 0.0 { draw_w / win_w } else { 1.0 };
+            let scale_y = if win_h > 0.0 { draw_h / win_h } else { 1.0 };
+            (draw_h, scale_x, scale_y)
+        }
+        None => (0.0, 1.0, 1.0),
+    };
+
+    let x = clip.0 * scale_x;
+    let y = clip.1 * scale_y;
+    let w = clip.2 * scale_x;
+    let h = clip.3 * scale_y;
+    let fb_y = (draw_h - (y + h)).max(0.0);
+
+    [x.max(0.0) as u32, fb_y as u32, w.max(0.0) as u32, h.max(0.0) as u32]
+}
+
+/// Intersects two `(x, y, w, h)` rectangles, clamping to a non-negative size.
+fn intersect_rect(a: (Scalar, Scalar, Scalar, Scalar),
+                  b: (Scalar, Scalar, Scalar, Scalar)) -> (Scalar, Scalar, Scalar, Scalar) {
+    let x1 = a.0.max(b.0);
+    let y1 = a.1.max(b.1);
+    let x2 = (a.0 + a.2).min(b.0 + b.2);
+    let y2 = (a.1 + a.3).min(b.1 + b.3);
+    (x1, y1, (x2 - x1).max(0.0), (y2 - y1).max(0.0))
+}
+
 #[pymethods]
 impl Canvas {
     /// Gets the size of the canvas.
@@ -225,15 +792,128 @@ impl Canvas {
         Ok(())
     }
 
+    /// Pushes a clip region, confining and translating subsequent drawing to
+    /// the given sub-rectangle until the matching `pop_clip`.
+    pub fn push_clip(&mut self, x: Scalar, y: Scalar, w: Scalar, h: Scalar) -> PyResult<()> {
+        assert_pyval!(w >= 0.0, "Clip width must be >= 0, got {}", w);
+        assert_pyval!(h >= 0.0, "Clip height must be >= 0, got {}", h);
+
+        self.draw_queue.push_back(DrawAction::PushClip { rect: (x, y, w, h) });
+        Ok(())
+    }
+
+    /// Pops the most recently pushed clip region.
+    pub fn pop_clip(&mut self) -> PyResult<()> {
+        self.draw_queue.push_back(DrawAction::PopClip);
+        Ok(())
+    }
+
     /// Draws a point on the canvas.
     pub fn draw_point(&mut self, point: Point, color: Color) -> PyResult<()> {
         self.draw_queue.push_back(DrawAction::Point(point, color));
         Ok(())
     }
 
-    /// Draws an image on the canvas.
-    pub fn draw_image(&mut self) -> PyResult<()> {
-        Err(exc::NotImplementedError::new("draw_image is not yet implemented")) // TODO
+    /// Sets the stroke state for subsequent paths.
+    pub fn line_style(&mut self, width: Scalar, color: Color) -> PyResult<()> {
+        self.line_style = Some((width, color));
+        Ok(())
+    }
+
+    /// Sets the fill for the region bracketed by the next `end_fill`.
+    pub fn begin_fill(&mut self, fill: Fill) -> PyResult<()> {
+        self.fill = Some(fill);
+        Ok(())
+    }
+
+    /// Commits the current path to the draw queue with the active stroke and
+    /// fill state, then clears the path and fill.
+    pub fn end_fill(&mut self) -> PyResult<()> {
+        let commands = mem::replace(&mut self.path, Vec::new());
+        self.draw_queue.push_back(DrawAction::Path {
+            commands,
+            line_style: self.line_style,
+            fill: self.fill.take(),
+        });
+        Ok(())
+    }
+
+    /// Starts a new subpath at the given point.
+    pub fn move_to(&mut self, x: Scalar, y: Scalar) -> PyResult<()> {
+        self.path.push(PathCommand::MoveTo((x, y)));
+        Ok(())
+    }
+
+    /// Appends a straight segment to the current subpath.
+    pub fn line_to(&mut self, x: Scalar, y: Scalar) -> PyResult<()> {
+        self.path.push(PathCommand::LineTo((x, y)));
+        Ok(())
+    }
+
+    /// Appends a quadratic Bézier segment through control `(cx, cy)` to `(x, y)`.
+    pub fn curve_to(&mut self, cx: Scalar, cy: Scalar, x: Scalar, y: Scalar) -> PyResult<()> {
+        self.path.push(PathCommand::CurveTo((cx, cy), (x, y)));
+        Ok(())
+    }
+
+    /// Commits the current path stroked only, with no fill, then clears the
+    /// path. Use this instead of `end_fill` for unfilled outlines.
+    pub fn stroke(&mut self) -> PyResult<()> {
+        let commands = mem::replace(&mut self.path, Vec::new());
+        self.draw_queue.push_back(DrawAction::Path {
+            commands,
+            line_style: self.line_style,
+            fill: None,
+        });
+        Ok(())
+    }
+
+    /// Resets the path builder state. Named `clear_path` rather than the
+    /// request's `clear` to avoid colliding with `Canvas.clear(color)`.
+    pub fn clear_path(&mut self) -> PyResult<()> {
+        self.path.clear();
+        self.line_style = None;
+        self.fill = None;
+        Ok(())
+    }
+
+    /// Decodes an image file and uploads it as a texture, returning an opaque
+    /// handle for use with `draw_image`.
+    pub fn load_texture(&mut self, path: String) -> PyResult<usize> {
+        let texture = {
+            let factory = match self.factory {
+                Some(ref mut factory) => factory,
+                None => return Err(exc::RuntimeError::new(
+                    "Textures can only be loaded once the frame has started",
+                )),
+            };
+            Texture::from_path(factory, &path, Flip::None, &TextureSettings::new())
+                .map_err(|err| exc::IOError::new(format!("Failed to load texture: {}", err)))?
+        };
+
+        let id = self.textures.len();
+        self.textures.push(texture);
+        Ok(id)
+    }
+
+    /// Draws a loaded texture on the canvas.
+    pub fn draw_image(&mut self,
+                      texture_id: usize,
+                      dest: Point,
+                      scale: Option<(Scalar, Scalar)>,
+                      src_rect: Option<(f64, f64, f64, f64)>,
+                      tint: Option<Color>) -> PyResult<()> {
+        assert_pyval!(texture_id < self.textures.len(),
+                      "Unknown texture id {}", texture_id);
+
+        self.draw_queue.push_back(DrawAction::Image {
+            texture_id,
+            dest,
+            scale,
+            src_rect: src_rect.map(|r| [r.0, r.1, r.2, r.3]),
+            tint,
+        });
+        Ok(())
     }
 
     /// Draws a circle on the canvas.
@@ -242,7 +922,7 @@ impl Canvas {
                        radius: Scalar,
                        line_color: Color,
                        line_width: Option<Scalar>,
-                       fill_color: Option<Color>) -> PyResult<()> {
+                       fill_color: Option<Fill>) -> PyResult<()> {
         self.draw_queue.push_back(DrawAction::Circle {
             center,
             radius,
@@ -275,7 +955,7 @@ impl Canvas {
                         vertices: Poly,
                         line_color: Color,
                         line_width: Option<Scalar>,
-                        fill_color: Option<Color>) -> PyResult<()> {
+                        fill_color: Option<Fill>) -> PyResult<()> {
         {
             let len = vertices.as_slice().len();
             assert_pyval!(len >= 3, "Polygon must have 3 or more vertices, got {}", len);
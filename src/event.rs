@@ -0,0 +1,161 @@
+use pyo3::prelude::*;
+
+use pyo3::py::class as pyclass;
+use pyo3::py::methods as pymethods;
+
+// Modifier mask bits.
+pub const MOD_SHIFT: u32 = 1 << 0;
+pub const MOD_CTRL: u32 = 1 << 1;
+pub const MOD_ALT: u32 = 1 << 2;
+pub const MOD_SUPER: u32 = 1 << 3;
+
+// Mouse button mask bits.
+pub const BTN_LEFT: u32 = 1 << 0;
+pub const BTN_RIGHT: u32 = 1 << 1;
+pub const BTN_MIDDLE: u32 = 1 << 2;
+
+/// A single input event delivered to the frame's event handler.
+#[pyclass]
+pub struct Event {
+    kind: &'static str,
+    mouse_x: f64,
+    mouse_y: f64,
+    mouse_dx: f64,
+    mouse_dy: f64,
+    scroll_x: f64,
+    scroll_y: f64,
+    key_code: i32,
+    char_code: i32,
+    modifiers: u32,
+    buttons: u32,
+    token: PyToken,
+}
+
+impl Event {
+    /// Constructs a new event from the current input state.
+    pub fn new<'p>(py: &'p Python, state: &EventState, kind: &'static str) -> Py<Event> {
+        py.init(|token| Event {
+            kind,
+            mouse_x: state.mouse.0,
+            mouse_y: state.mouse.1,
+            mouse_dx: state.mouse_delta.0,
+            mouse_dy: state.mouse_delta.1,
+            scroll_x: state.scroll.0,
+            scroll_y: state.scroll.1,
+            key_code: state.key_code,
+            char_code: state.char_code,
+            modifiers: state.modifiers,
+            buttons: state.buttons,
+            token,
+        }).unwrap()
+    }
+}
+
+#[pymethods]
+impl Event {
+    /// Returns the event type (e.g. `mouse_down`, `key_up`, `scroll`).
+    pub fn get_type(&self) -> PyResult<String> {
+        Ok(self.kind.to_owned())
+    }
+
+    /// Returns the cursor x position.
+    pub fn get_mouse_x(&self) -> PyResult<f64> {
+        Ok(self.mouse_x)
+    }
+
+    /// Returns the cursor y position.
+    pub fn get_mouse_y(&self) -> PyResult<f64> {
+        Ok(self.mouse_y)
+    }
+
+    /// Returns the cursor x movement since the last move event.
+    pub fn get_mouse_dx(&self) -> PyResult<f64> {
+        Ok(self.mouse_dx)
+    }
+
+    /// Returns the cursor y movement since the last move event.
+    pub fn get_mouse_dy(&self) -> PyResult<f64> {
+        Ok(self.mouse_dy)
+    }
+
+    /// Returns the horizontal scroll amount.
+    pub fn get_scroll_x(&self) -> PyResult<f64> {
+        Ok(self.scroll_x)
+    }
+
+    /// Returns the vertical scroll amount.
+    pub fn get_scroll_y(&self) -> PyResult<f64> {
+        Ok(self.scroll_y)
+    }
+
+    /// Returns the key code, or `0` if the event carries no key.
+    pub fn get_key_code(&self) -> PyResult<i32> {
+        Ok(self.key_code)
+    }
+
+    /// Returns the unicode code point, or `0` if the event carries no character.
+    pub fn get_char_code(&self) -> PyResult<i32> {
+        Ok(self.char_code)
+    }
+
+    /// Returns the bitmask of held modifiers (shift/ctrl/alt/super).
+    pub fn get_modifiers(&self) -> PyResult<u32> {
+        Ok(self.modifiers)
+    }
+
+    /// Returns the bitmask of held mouse buttons.
+    pub fn get_buttons(&self) -> PyResult<u32> {
+        Ok(self.buttons)
+    }
+}
+
+/// Retained input state, carried across frames to track deltas and held keys.
+pub struct EventState {
+    pub mouse: (f64, f64),
+    pub mouse_delta: (f64, f64),
+    pub scroll: (f64, f64),
+    pub key_code: i32,
+    pub char_code: i32,
+    pub modifiers: u32,
+    pub buttons: u32,
+}
+
+impl EventState {
+    /// Constructs a zeroed input state.
+    pub fn new() -> EventState {
+        EventState {
+            mouse: (0.0, 0.0),
+            mouse_delta: (0.0, 0.0),
+            scroll: (0.0, 0.0),
+            key_code: 0,
+            char_code: 0,
+            modifiers: 0,
+            buttons: 0,
+        }
+    }
+
+    /// Updates the cursor position, recording the delta from the previous position.
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        self.mouse_delta = (x - self.mouse.0, y - self.mouse.1);
+        self.mouse = (x, y);
+    }
+
+    /// Sets or clears a modifier bit in the mask.
+    pub fn set_modifier(&mut self, bit: u32, pressed: bool) {
+        if pressed {
+            self.modifiers |= bit;
+        } else {
+            self.modifiers &= !bit;
+        }
+    }
+
+    /// ORs a mouse button bit into the mask on press.
+    pub fn press_button(&mut self, bit: u32) {
+        self.buttons |= bit;
+    }
+
+    /// Clears a mouse button bit from the mask on release.
+    pub fn release_button(&mut self, bit: u32) {
+        self.buttons &= !bit;
+    }
+}
@@ -9,6 +9,7 @@ use glfw_window::GlfwWindow;
 use glfw;
 
 use canvas::Canvas;
+use event::{self, Event, EventState};
 
 type FrameWindow = PistonWindow<GlfwWindow>;
 
@@ -20,9 +21,16 @@ pub struct Frame {
     draw_handler: Option<PyObject>,
     event_handler: Option<PyObject>,
     started: bool,
+    ui_mode: bool,
+    needs_refresh: bool,
+    ticks: u32,
     token: PyToken,
 }
 
+// Trailing frames drawn after the last change so double-buffered transitions
+// settle before the frame goes idle.
+const TRAILING_FRAMES: u32 = 3;
+
 #[pymethods(gc)]
 impl Frame {
     /// Starts the frame draw and event handlers.
@@ -34,8 +42,43 @@ impl Frame {
         }
         self.started = true;
 
+        {
+            // Wire the window factory into the canvas so textures can be loaded.
+            let factory = self.window.factory.clone();
+            let gil = Python::acquire_gil();
+            let py = gil.python();
+            self.canvas.as_mut(py).set_factory(factory);
+        }
+
+        let mut state = EventState::new();
+
         while let Some(e) = self.window.next() {
+            // In UI mode any input event schedules a redraw.
+            if self.ui_mode && is_input_event(&e) {
+                self.needs_refresh = true;
+            }
+
+            // Dispatch input events to the event handler before rendering.
+            if self.event_handler.is_some() {
+                if let Err(err) = self.dispatch_event(&e, &mut state) {
+                    return Err(err);
+                }
+            }
+
             if let Some(_) = e.render_args() {
+                // In UI mode, skip drawing once idle, but emit a few trailing
+                // frames after the last change so transitions settle.
+                if self.ui_mode {
+                    if self.needs_refresh {
+                        self.needs_refresh = false;
+                        self.ticks = TRAILING_FRAMES;
+                    } else if self.ticks > 0 {
+                        self.ticks -= 1;
+                    } else {
+                        continue;
+                    }
+                }
+
                 let gil = Python::acquire_gil();
 
                 let mut canvas = &self.canvas;
@@ -63,13 +106,106 @@ impl Frame {
                     canvas.draw_canvas(&c, g)
                 });
             }
+        }
 
-            // TODO: event handling
+        Ok(())
+    }
+
+    /// Translates a piston event into a Python-visible `Event` and invokes the
+    /// event handler for each matching input event, propagating any `PyErr`.
+    fn dispatch_event<E: GenericEvent>(&mut self, e: &E, state: &mut EventState) -> PyResult<()> {
+        // Cursor movement carries a delta since the last move.
+        if let Some(pos) = e.mouse_cursor_args() {
+            state.move_to(pos[0], pos[1]);
+            self.fire_event(state, "mouse_move")?;
+        }
+
+        if let Some(scroll) = e.mouse_scroll_args() {
+            state.scroll = (scroll[0], scroll[1]);
+            self.fire_event(state, "scroll")?;
+            state.scroll = (0.0, 0.0);
+        }
+
+        if let Some(button) = e.press_args() {
+            let kind = self.apply_button(state, button, true);
+            self.fire_event(state, kind)?;
+            state.key_code = 0;
+        }
+
+        if let Some(button) = e.release_args() {
+            let kind = self.apply_button(state, button, false);
+            self.fire_event(state, kind)?;
+            state.key_code = 0;
+        }
+
+        if let Some(text) = e.text_args() {
+            if let Some(ch) = text.chars().next() {
+                state.char_code = ch as i32;
+                self.fire_event(state, "char")?;
+                state.char_code = 0;
+            }
+        }
+
+        if let Some(_) = e.resize_args() {
+            self.fire_event(state, "resize")?;
+        }
+
+        if let Some(focused) = e.focus_args() {
+            self.fire_event(state, if focused { "focus" } else { "blur" })?;
         }
 
         Ok(())
     }
 
+    /// Updates the retained button/modifier/key state for a button press or
+    /// release and returns the event type to report.
+    fn apply_button(&self, state: &mut EventState, button: Button, pressed: bool) -> &'static str {
+        match button {
+            Button::Mouse(mouse) => {
+                let bit = match mouse {
+                    MouseButton::Left => event::BTN_LEFT,
+                    MouseButton::Right => event::BTN_RIGHT,
+                    MouseButton::Middle => event::BTN_MIDDLE,
+                    _ => 0,
+                };
+                if pressed {
+                    state.press_button(bit);
+                    "mouse_down"
+                } else {
+                    state.release_button(bit);
+                    "mouse_up"
+                }
+            }
+            Button::Keyboard(key) => {
+                state.key_code = key as i32;
+                match key {
+                    Key::LShift | Key::RShift => state.set_modifier(event::MOD_SHIFT, pressed),
+                    Key::LCtrl | Key::RCtrl => state.set_modifier(event::MOD_CTRL, pressed),
+                    Key::LAlt | Key::RAlt => state.set_modifier(event::MOD_ALT, pressed),
+                    Key::LGui | Key::RGui => state.set_modifier(event::MOD_SUPER, pressed),
+                    _ => (),
+                }
+                if pressed { "key_down" } else { "key_up" }
+            }
+            _ => {
+                if pressed { "key_down" } else { "key_up" }
+            }
+        }
+    }
+
+    /// Builds an `Event` from the current state and calls the event handler.
+    fn fire_event(&self, state: &EventState, kind: &'static str) -> PyResult<()> {
+        if let Some(ref handler) = self.event_handler {
+            let gil = Python::acquire_gil();
+            let py = gil.python();
+
+            let event = Event::new(&py, state, kind);
+            let args = PyTuple::new(py, &[&event]);
+            handler.call(py, args, NoArgs)?;
+        }
+        Ok(())
+    }
+
     // Event handlers.
 
     /// Sets the draw handler for the frame.
@@ -84,6 +220,20 @@ impl Frame {
         Ok(())
     }
 
+    /// Enables or disables UI mode, in which the frame only redraws after an
+    /// input event or an explicit `refresh` call.
+    pub fn set_ui_mode(&mut self, ui_mode: bool) -> PyResult<()> {
+        self.ui_mode = ui_mode;
+        self.needs_refresh = true;
+        Ok(())
+    }
+
+    /// Requests a redraw while in UI mode.
+    pub fn refresh(&mut self) -> PyResult<()> {
+        self.needs_refresh = true;
+        Ok(())
+    }
+
     // Window functions.
 
     /// Shows the window.
@@ -276,7 +426,19 @@ impl PyGCProtocol for Frame {
     }
 }
 
-pub fn create_frame<'p>(py: &'p Python, title: String, width: u32, height: u32, resizable: bool, fullscreen: bool) -> Py<Frame> {
+/// Returns `true` if the event carries user input.
+fn is_input_event<E: GenericEvent>(e: &E) -> bool {
+    e.mouse_cursor_args().is_some()
+        || e.mouse_relative_args().is_some()
+        || e.mouse_scroll_args().is_some()
+        || e.press_args().is_some()
+        || e.release_args().is_some()
+        || e.text_args().is_some()
+        || e.resize_args().is_some()
+        || e.focus_args().is_some()
+}
+
+pub fn create_frame<'p>(py: &'p Python, title: String, width: u32, height: u32, resizable: bool, fullscreen: bool, ui_mode: bool) -> Py<Frame> {
     const OPENGL_VERSION: OpenGL = OpenGL::V3_3;
     const SAMPLES: u8 = 4;
 
@@ -300,6 +462,9 @@ pub fn create_frame<'p>(py: &'p Python, title: String, width: u32, height: u32,
         draw_handler: None,
         event_handler: None,
         started: false,
+        ui_mode,
+        needs_refresh: true,
+        ticks: 0,
         token,
     }).unwrap()
 }
\ No newline at end of file
@@ -12,21 +12,24 @@ mod macros;
 
 mod frame;
 mod canvas;
+mod event;
 
 #[pymodinit(pygui)]
 fn init_mod(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<frame::Frame>()?;
     m.add_class::<canvas::Canvas>()?;
+    m.add_class::<event::Event>()?;
 
     #[pyfn(m, "create_frame")]
-    fn create_frame(py: Python, title: String, width: i32, height: i32, resizable: Option<bool>, fullscreen: Option<bool>) -> PyResult<Py<frame::Frame>> {
+    fn create_frame(py: Python, title: String, width: i32, height: i32, resizable: Option<bool>, fullscreen: Option<bool>, ui_mode: Option<bool>) -> PyResult<Py<frame::Frame>> {
         let resizable = resizable.unwrap_or(true);
         let fullscreen = fullscreen.unwrap_or(false);
+        let ui_mode = ui_mode.unwrap_or(false);
 
         assert_pyval!(width > 0, "Width must be > 0, got {}", width);
         assert_pyval!(height > 0, "Height must be > 0, got {}", width);
 
-        Ok(frame::create_frame(&py, title, width as u32, height as u32, resizable, fullscreen))
+        Ok(frame::create_frame(&py, title, width as u32, height as u32, resizable, fullscreen, ui_mode))
     }
 
     Ok(())